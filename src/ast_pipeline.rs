@@ -0,0 +1,253 @@
+//! AST-based replacement for the old regex passes over raw markdown text.
+//!
+//! `fix_internal_links`, `fix_section_links`, `strip_github_blob_sha` and
+//! `fix_table_content`/`fix_long_lines` used to scan the file byte-by-byte
+//! with regexes, which meant an `/owner/repo` path sitting inside an inline
+//! code span or fenced code block got rewritten just like a real link, and
+//! long-line wrapping could split a URL or `[text](url)` across a newline.
+//! Parsing into comrak's arena-allocated CommonMark AST lets us restrict
+//! each rewrite to the node kind it actually applies to.
+
+use std::cell::RefCell;
+
+use comrak::nodes::{Ast, AstNode, NodeValue};
+use comrak::{format_commonmark, parse_document, Arena, ComrakOptions};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+const SECTION_ANCHORS: &[(&str, &str)] = &[
+    ("Networking Section", "networking-configuration"),
+    ("Virtual Environment Section", "virtual-environment-setup"),
+    ("Module Import Section", "module-import-issues"),
+    ("WSL.exe Section", "wslexe-issues"),
+    ("Path Translation Section", "path-translation-issues"),
+    ("Performance Section", "performance-optimization"),
+    ("Line Ending Section", "line-ending-issues"),
+    ("Distribution Section", "distribution-selection"),
+];
+
+/// Matches a root-relative `/owner/repo[/...]` path. Shared by
+/// `is_internal_link` and `rewrite_link_url`, compiled once: `walk` calls
+/// `rewrite_link_url` per `Link`/`Image` node, so a per-call `Regex::new`
+/// here would recompile on every link in every document.
+static INTERNAL_LINK_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(/[^/\s]+/[^/\s]+(?:/[^\s]*)?)$").unwrap());
+
+/// One compiled regex per `SECTION_ANCHORS` entry, matching a GitHub
+/// blob URL pointing at that section heading's old anchor.
+static SECTION_ANCHOR_RES: Lazy<Vec<(Regex, &'static str)>> = Lazy::new(|| {
+    SECTION_ANCHORS
+        .iter()
+        .map(|(section, anchor)| {
+            let pattern = format!(
+                r"^https://github\.com/(?P<owner>[^/]+)/(?P<repo>[^/]+)/blob/(?P<sha>[0-9a-f]{{7,40}})/{}$",
+                regex::escape(section)
+            );
+            (Regex::new(&pattern).unwrap(), *anchor)
+        })
+        .collect()
+});
+
+static BLOB_SHA_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^https://github\.com/([^/]+)/([^/]+)/blob/([0-9a-f]{7,40})/(.*)$").unwrap()
+});
+
+fn comrak_options() -> ComrakOptions {
+    let mut options = ComrakOptions::default();
+    options.extension.table = true;
+    options.extension.strikethrough = true;
+    options.extension.autolink = true;
+    options.render.width = 80;
+    options.render.hardbreaks = false;
+    options
+}
+
+/// Whether `url` is a root-relative `/owner/repo[/...]` path that
+/// `rewrite_link_url` would GitHub-ify. Exposed so `json_model` can flag
+/// which links in its link graph were internal.
+pub(crate) fn is_internal_link(url: &str) -> bool {
+    INTERNAL_LINK_RE.is_match(url)
+}
+
+/// Rewrite a single link target the same way `fix_internal_links`,
+/// `fix_section_links` and `strip_github_blob_sha` used to, but only ever
+/// called on a `NodeValue::Link.url`, never on arbitrary text.
+pub(crate) fn rewrite_link_url(url: &str) -> String {
+    let mut url = if let Some(caps) = INTERNAL_LINK_RE.captures(url) {
+        format!("https://github.com{}", &caps[1])
+    } else {
+        url.to_string()
+    };
+
+    for (re, anchor) in SECTION_ANCHOR_RES.iter() {
+        if let Some(caps) = re.captures(&url) {
+            url = format!(
+                "https://github.com/{}/{}/blob/{}/README.md#{}",
+                &caps["owner"], &caps["repo"], &caps["sha"], anchor
+            );
+        }
+    }
+
+    if let Some(caps) = BLOB_SHA_RE.captures(&url) {
+        url = format!("https://github.com/{}/{}/{}", &caps[1], &caps[2], &caps[4]);
+    }
+
+    url
+}
+
+/// Split a table cell's rendered text into lines of at most `max_width`
+/// bytes at word boundaries (a single word longer than that stays whole
+/// on its own line), mirroring the old `fix_table_rows` wrapping but operating
+/// on the cell's own text content instead of a `|`-split string that
+/// could clip mid-link. The caller joins the returned lines with
+/// `NodeValue::HtmlInline("<br>")` nodes: GFM pipe tables are one
+/// physical line per row, so a real `LineBreak` (rendered as a
+/// backslash-newline) splits the row into multiple `TableRow`s with
+/// mismatched cell counts, and a literal `<br>` inside a `Text` node gets
+/// backslash-escaped by comrak's commonmark writer. `HtmlInline` is the
+/// only node kind that reaches the output verbatim on the same line.
+fn wrap_cell_text(text: &str, max_width: usize) -> Vec<String> {
+    if text.len() <= max_width {
+        return vec![text.to_string()];
+    }
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        if current.is_empty() {
+            current.push_str(word);
+        } else if current.len() + 1 + word.len() <= max_width {
+            current.push(' ');
+            current.push_str(word);
+        } else {
+            lines.push(std::mem::take(&mut current));
+            current.push_str(word);
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Hard-wrap a fenced/indented code block's literal contents at
+/// `max_width` characters per line. Every longer line is split, whereas
+/// the old `fix_long_lines` regex pass only split code lines longer than
+/// twice its limit. `render.width` only reflows prose `Text`
+/// nodes, never `CodeBlock` literal content, so without this, long lines
+/// inside fenced code would pass through unwrapped and overflow the PDF.
+fn wrap_code_block_literal(literal: &str, max_width: usize) -> String {
+    let mut result = String::with_capacity(literal.len());
+    for line in literal.lines() {
+        let chars: Vec<char> = line.chars().collect();
+        if chars.len() <= max_width {
+            result.push_str(line);
+            result.push('\n');
+            continue;
+        }
+        for chunk in chars.chunks(max_width) {
+            result.extend(chunk.iter());
+            result.push('\n');
+        }
+    }
+    result
+}
+
+/// Allocate a new, childless AST node holding `value`. Used to splice
+/// `HtmlInline`/`Text` siblings into the tree in place of a single
+/// wrapped-text node.
+fn make_node<'a>(arena: &'a Arena<AstNode<'a>>, value: NodeValue) -> &'a AstNode<'a> {
+    arena.alloc(AstNode::new(RefCell::new(Ast::new(value, (1, 1).into()))))
+}
+
+fn node_is_table_cell(node: &AstNode) -> bool {
+    matches!(node.data.borrow().value, NodeValue::TableCell)
+}
+
+/// Walk the tree, rewriting link URLs, wrapping table-cell text and
+/// hard-wrapping code-block lines in place. No link rewriting or prose
+/// reflow happens under `Code`/`CodeBlock`, because we never descend into
+/// them; a `CodeBlock`'s own literal is only line-wrapped.
+fn walk<'a>(arena: &'a Arena<AstNode<'a>>, node: &'a AstNode<'a>, in_table_cell: bool) {
+    let in_table_cell = in_table_cell || node_is_table_cell(node);
+
+    {
+        let mut ast = node.data.borrow_mut();
+        match &mut ast.value {
+            NodeValue::Link(link) | NodeValue::Image(link) => {
+                link.url = rewrite_link_url(&link.url);
+            }
+            NodeValue::CodeBlock(code_block) => {
+                code_block.literal = wrap_code_block_literal(&code_block.literal, 80);
+            }
+            _ => {}
+        }
+    }
+
+    if in_table_cell {
+        let text = match &node.data.borrow().value {
+            NodeValue::Text(text) => Some(text.clone()),
+            _ => None,
+        };
+        if let Some(text) = text {
+            let lines = wrap_cell_text(&text, 30);
+            if lines.len() > 1 {
+                node.data.borrow_mut().value = NodeValue::Text(lines[0].clone());
+                let mut insert_after = node;
+                for line in &lines[1..] {
+                    let br = make_node(arena, NodeValue::HtmlInline("<br>".to_string()));
+                    insert_after.insert_after(br);
+                    let text_node = make_node(arena, NodeValue::Text(line.clone()));
+                    br.insert_after(text_node);
+                    insert_after = text_node;
+                }
+            }
+        }
+    }
+
+    // `Code`/`CodeBlock` nodes have no children carrying rewritable links
+    // or reflowable prose, so recursing into their (empty) child list is a
+    // no-op; we still skip explicitly for clarity.
+    let value_is_code = matches!(
+        node.data.borrow().value,
+        NodeValue::Code(_) | NodeValue::CodeBlock(_)
+    );
+    if value_is_code {
+        return;
+    }
+
+    for child in node.children() {
+        walk(arena, child, in_table_cell);
+    }
+}
+
+/// Parse `text` into a CommonMark AST, rewrite internal/section/blob-sha
+/// links, wrap long table-cell text and long code-block lines, then
+/// render back to markdown. Comrak's own commonmark renderer handles
+/// long-line reflow for prose via `render.width`, so `fix_long_lines`'s
+/// manual word-wrap is only still needed for code blocks (see
+/// `wrap_code_block_literal`), which `render.width` never touches.
+///
+/// Known regression versus the old regex passes: `format_commonmark`
+/// re-serializes the *whole* document in comrak's own canonical style,
+/// not just the nodes this pass rewrites, so unrelated surface syntax
+/// (bullet markers, emphasis delimiters, ordered-list spacing, adjacent
+/// lists of different types) is normalized to comrak's conventions even
+/// where the input already had a valid, different-looking form. This is
+/// an accepted tradeoff for getting link/table rewrites off of
+/// regexes-over-raw-text; revisit if diff noise on real wikis becomes a
+/// problem.
+pub fn process_with_ast(text: &str) -> String {
+    let arena = Arena::new();
+    let options = comrak_options();
+    let root = parse_document(&arena, text, &options);
+
+    walk(&arena, root, false);
+
+    let mut output = Vec::new();
+    if format_commonmark(root, &options, &mut output).is_err() {
+        return text.to_string();
+    }
+
+    String::from_utf8(output).unwrap_or_else(|_| text.to_string())
+}
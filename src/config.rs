@@ -0,0 +1,93 @@
+//! `deepwiki.toml` configuration, layered over built-in defaults the way
+//! mdBook's own `Config` merges a parsed TOML file over its defaults.
+//! Lets users pick letter paper, a different mono font, a cover logo, or
+//! a deeper TOC without patching `convert_project_to_pdf`.
+
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct PdfTitleConfig {
+    /// Custom LaTeX title-page template. `{title}` is substituted with
+    /// the project name, with underscores escaped as `\_` (no other LaTeX
+    /// special characters are escaped). Falls back to the crate's
+    /// built-in template when unset.
+    pub template: Option<String>,
+}
+
+impl Default for PdfTitleConfig {
+    fn default() -> Self {
+        PdfTitleConfig { template: None }
+    }
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct PdfConfig {
+    pub engine: String,
+    pub margin: String,
+    pub mainfont: String,
+    pub monofont: String,
+    pub fontsize: String,
+    pub papersize: String,
+    pub toc_depth: u8,
+    pub title: PdfTitleConfig,
+}
+
+impl Default for PdfConfig {
+    fn default() -> Self {
+        PdfConfig {
+            engine: "xelatex".to_string(),
+            margin: "0.7in".to_string(),
+            mainfont: "Helvetica".to_string(),
+            monofont: "Menlo".to_string(),
+            fontsize: "9pt".to_string(),
+            papersize: "a4".to_string(),
+            toc_depth: 2,
+            title: PdfTitleConfig::default(),
+        }
+    }
+}
+
+/// A `[[preprocessor]]` entry: an external command that gets a project's
+/// file set piped to it as JSON before `consolidate_project_markdown`
+/// runs, mirroring mdBook's preprocessor model.
+#[derive(Deserialize, Clone)]
+pub struct PreprocessorConfig {
+    pub command: String,
+}
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct Config {
+    pub pdf: PdfConfig,
+    pub preprocessor: Vec<PreprocessorConfig>,
+}
+
+/// Load `deepwiki.toml` from `path`, falling back to built-in defaults for
+/// any section/key it omits, or entirely if `path` is `None`.
+pub fn load(path: Option<&Path>) -> Config {
+    let path = match path {
+        Some(p) => p,
+        None => return Config::default(),
+    };
+
+    let text = match fs::read_to_string(path) {
+        Ok(t) => t,
+        Err(e) => {
+            eprintln!("Warning: failed to read config '{}': {}", path.display(), e);
+            return Config::default();
+        }
+    };
+
+    match toml::from_str(&text) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Warning: failed to parse config '{}': {}", path.display(), e);
+            Config::default()
+        }
+    }
+}
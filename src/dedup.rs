@@ -0,0 +1,112 @@
+//! Duplicate wiki-page collapsing for `process_directory`, inspired by
+//! pkgcheck's blake2-based duplicate finder: DeepWiki exports sometimes
+//! repeat the same page (e.g. an "Overview") under two sections, which
+//! only bloats the PDF `consolidate_project_markdown` produces. This
+//! hashes each project's markdown bodies, collapses exact duplicates onto
+//! the first file in a group, and rewrites links to the duplicates
+//! through the same mapping mechanism `apply_readme_ordinal` uses for
+//! renames.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use blake2::{Blake2b512, Digest};
+use walkdir::WalkDir;
+
+use crate::{is_markdown, rewrite_markdown_links};
+
+/// Strip the leading H1 (DeepWiki repeats the page title as a heading,
+/// which would otherwise make unrelated pages collide) and trailing
+/// whitespace, so near-identical bodies hash the same.
+fn normalize_for_hash(text: &str) -> String {
+    let mut lines: Vec<&str> = text.lines().collect();
+    if lines.first().map_or(false, |line| line.trim_start().starts_with("# ")) {
+        lines.remove(0);
+    }
+    lines.join("\n").trim_end().to_string()
+}
+
+fn digest_hex(text: &str) -> String {
+    let mut hasher = Blake2b512::new();
+    hasher.update(text.as_bytes());
+    hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Hash every non-README markdown file directly under `dir`, group by
+/// digest, and for each group with more than one member rewrite links to
+/// the duplicates so they point at the first (alphabetically earliest)
+/// survivor, then remove the duplicate files. Returns every path touched
+/// (rewritten files plus removed duplicates), the same shape
+/// `apply_readme_ordinal` returns for `--dry-run` reporting.
+pub fn dedup_directory(dir: &Path, dry_run: bool) -> Vec<PathBuf> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| {
+            is_markdown(p) && p.file_name().map_or(false, |f| f != "README.md")
+        })
+        .collect();
+    entries.sort();
+
+    let mut groups: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    for path in entries {
+        let text = match fs::read_to_string(&path) {
+            Ok(t) => t,
+            Err(_) => continue,
+        };
+        let digest = digest_hex(&normalize_for_hash(&text));
+        groups.entry(digest).or_default().push(path);
+    }
+
+    let mut mapping: HashMap<String, String> = HashMap::new();
+    let mut duplicates: Vec<PathBuf> = Vec::new();
+    for paths in groups.values() {
+        if paths.len() < 2 {
+            continue;
+        }
+        let survivor_name = paths[0]
+            .file_name()
+            .and_then(|f| f.to_str())
+            .unwrap_or("")
+            .to_string();
+        for duplicate in &paths[1..] {
+            if let Some(name) = duplicate.file_name().and_then(|f| f.to_str()) {
+                mapping.insert(name.to_string(), survivor_name.clone());
+            }
+            duplicates.push(duplicate.clone());
+        }
+    }
+
+    if mapping.is_empty() {
+        return Vec::new();
+    }
+
+    let mut changed: Vec<PathBuf> = Vec::new();
+
+    for entry in WalkDir::new(dir).into_iter().filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if is_markdown(path) {
+            if let Ok(original) = fs::read_to_string(path) {
+                let updated = rewrite_markdown_links(&original, &mapping);
+                if updated != original {
+                    changed.push(path.to_path_buf());
+                    if !dry_run {
+                        let _ = fs::write(path, &updated);
+                    }
+                }
+            }
+        }
+    }
+
+    for duplicate in duplicates {
+        changed.push(duplicate.clone());
+        if !dry_run {
+            let _ = fs::remove_file(&duplicate);
+        }
+    }
+
+    changed
+}
@@ -0,0 +1,27 @@
+//! Recursively collect the rendered text of a heading (or any inline
+//! container) node, the way comrak's own header-anchor example does: walk
+//! the children concatenating `Text`/`Code` literals and turning
+//! `SoftBreak`/`LineBreak` into spaces. This copes with headings like
+//! `# The [API](...) Guide` that a plain `starts_with('#')`/string-slice
+//! approach mangles.
+
+use comrak::nodes::{AstNode, NodeValue};
+
+pub(crate) fn collect_text<'a>(node: &'a AstNode<'a>) -> String {
+    let mut text = String::new();
+    collect_into(node, &mut text);
+    text.trim().to_string()
+}
+
+fn collect_into<'a>(node: &'a AstNode<'a>, out: &mut String) {
+    match &node.data.borrow().value {
+        NodeValue::Text(literal) => out.push_str(literal),
+        NodeValue::Code(code) => out.push_str(&code.literal),
+        NodeValue::SoftBreak | NodeValue::LineBreak => out.push(' '),
+        _ => {
+            for child in node.children() {
+                collect_into(child, out);
+            }
+        }
+    }
+}
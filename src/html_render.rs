@@ -0,0 +1,127 @@
+//! Native HTML rendering, the non-PDF analogue of `--pdf`.
+//!
+//! Unlike `convert_project_to_pdf`, which shells out to pandoc/xelatex,
+//! this renders each processed document to standalone HTML in-process
+//! using comrak's own HTML renderer, so users can publish the cleaned
+//! DeepWiki docs directly without external tooling.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use comrak::nodes::{AstNode, NodeHtmlBlock, NodeValue};
+use comrak::{format_html, parse_document, Arena, ComrakOptions};
+use walkdir::WalkDir;
+
+use crate::{heading_text, is_markdown};
+
+const MERMAID_SCRIPT: &str = r#"<script src="https://cdn.jsdelivr.net/npm/mermaid@10/dist/mermaid.min.js"></script>
+<script>mermaid.initialize({ startOnLoad: true });</script>"#;
+
+fn comrak_options() -> ComrakOptions {
+    let mut options = ComrakOptions::default();
+    options.extension.table = true;
+    options.extension.strikethrough = true;
+    options.extension.autolink = true;
+    options
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Replace ` ```mermaid ` fenced code blocks with raw `<pre class="mermaid">`
+/// HTML blocks, so the browser (and mermaid.js) sees the diagram source
+/// verbatim instead of an escaped, syntax-highlighted code listing. Returns
+/// whether any mermaid block was found, so the caller knows whether to
+/// inject the mermaid.js include.
+fn preserve_mermaid_fences<'a>(root: &'a AstNode<'a>) -> bool {
+    let mut has_mermaid = false;
+    for node in root.descendants() {
+        let mut ast = node.data.borrow_mut();
+        if let NodeValue::CodeBlock(block) = &ast.value {
+            if block.info.trim() == "mermaid" {
+                has_mermaid = true;
+                let html = format!("<pre class=\"mermaid\">\n{}</pre>\n", escape_html(&block.literal));
+                ast.value = NodeValue::HtmlBlock(NodeHtmlBlock {
+                    block_type: 0,
+                    literal: html,
+                });
+            }
+        }
+    }
+    has_mermaid
+}
+
+/// Pull the first heading's rendered text to use as the page `<title>`,
+/// the same way `main.rs::first_heading_title` does, so a heading like
+/// `# The [API](https://x) Guide` yields `The API Guide` rather than the
+/// literal markdown a naive `#`-prefix scan would leave in.
+fn extract_title(text: &str) -> Option<String> {
+    let arena = Arena::new();
+    let options = ComrakOptions::default();
+    let root = parse_document(&arena, text, &options);
+
+    for node in root.descendants() {
+        if matches!(node.data.borrow().value, NodeValue::Heading(_)) {
+            let title = heading_text::collect_text(node);
+            if !title.is_empty() {
+                return Some(title);
+            }
+        }
+    }
+    None
+}
+
+/// Render one markdown document to a standalone HTML page.
+pub fn render_document_to_html(text: &str) -> String {
+    let arena = Arena::new();
+    let options = comrak_options();
+    let root = parse_document(&arena, text, &options);
+
+    let has_mermaid = preserve_mermaid_fences(root);
+
+    let mut body = Vec::new();
+    format_html(root, &options, &mut body).unwrap_or_default();
+    let body = String::from_utf8(body).unwrap_or_default();
+
+    let title = extract_title(text).unwrap_or_else(|| "Untitled".to_string());
+    let mermaid_include = if has_mermaid { MERMAID_SCRIPT } else { "" };
+
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n{}{}\n</body>\n</html>\n",
+        escape_html(&title),
+        body,
+        mermaid_include,
+    )
+}
+
+/// Render every markdown file under `output_dir` to a mirrored `.html`
+/// file under `html_dir`, preserving the directory structure.
+pub fn process_directory_to_html(output_dir: &Path, html_dir: &Path) -> Vec<PathBuf> {
+    let mut rendered = Vec::new();
+
+    for entry in WalkDir::new(output_dir).into_iter().filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if !is_markdown(path) {
+            continue;
+        }
+
+        let rel_path = path.strip_prefix(output_dir).unwrap_or(path);
+        let mut out_path = html_dir.join(rel_path);
+        out_path.set_extension("html");
+
+        if let Ok(content) = fs::read_to_string(path) {
+            let html = render_document_to_html(&content);
+            if let Some(parent) = out_path.parent() {
+                let _ = fs::create_dir_all(parent);
+            }
+            if fs::write(&out_path, &html).is_ok() {
+                rendered.push(out_path);
+            }
+        }
+    }
+
+    rendered
+}
@@ -0,0 +1,226 @@
+//! Structured JSON model of a processed document for `--json`: the
+//! heading outline, the link graph (original vs. rewritten target, and
+//! whether it was an internal `/owner/repo` link that got GitHub-ified),
+//! and a normalized graph for each mermaid block. Downstream tooling can
+//! consume this instead of re-parsing markdown itself.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use comrak::nodes::NodeValue;
+use comrak::{parse_document, Arena, ComrakOptions};
+use serde::Serialize;
+use walkdir::WalkDir;
+
+use crate::ast_pipeline::{is_internal_link, rewrite_link_url};
+use crate::heading_text::collect_text;
+use crate::slug::slugify;
+use crate::{extract_mermaid_blocks, fix_malformed_nodes, is_markdown, parse_flowchart_graph, parse_sequence_participant_aliases, sanitize_node_labels};
+
+#[derive(Serialize)]
+pub struct HeadingEntry {
+    pub level: u8,
+    pub text: String,
+    pub slug: String,
+}
+
+#[derive(Serialize)]
+pub struct LinkEntry {
+    pub original: String,
+    pub rewritten: String,
+    pub internal: bool,
+}
+
+#[derive(Serialize)]
+pub struct FlowchartNode {
+    pub id: String,
+    pub label: Option<String>,
+}
+
+/// Public mermaid flowchart edge, mirroring the internal `Edge` that
+/// `move_branch_labels` reconstructs, but serializable on its own.
+#[derive(Serialize)]
+pub struct Edge {
+    pub src: String,
+    pub dst: String,
+    pub arrow: String,
+    pub label: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct ParticipantAlias {
+    pub name: String,
+    pub alias: String,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind")]
+pub enum MermaidGraph {
+    Flowchart { nodes: Vec<FlowchartNode>, edges: Vec<Edge> },
+    Sequence { participants: Vec<ParticipantAlias> },
+}
+
+#[derive(Serialize)]
+pub struct DocumentModel {
+    pub path: String,
+    pub headings: Vec<HeadingEntry>,
+    pub links: Vec<LinkEntry>,
+    pub mermaid: Vec<MermaidGraph>,
+}
+
+fn comrak_options() -> ComrakOptions {
+    let mut options = ComrakOptions::default();
+    options.extension.table = true;
+    options
+}
+
+fn extract_headings(text: &str) -> Vec<HeadingEntry> {
+    let arena = Arena::new();
+    let options = comrak_options();
+    let root = parse_document(&arena, text, &options);
+
+    let mut headings = Vec::new();
+    for node in root.descendants() {
+        let level = match &node.data.borrow().value {
+            NodeValue::Heading(heading) => heading.level,
+            _ => continue,
+        };
+        let rendered = collect_text(node);
+        headings.push(HeadingEntry {
+            level,
+            slug: slugify(&rendered),
+            text: rendered,
+        });
+    }
+    headings
+}
+
+fn extract_links(text: &str) -> Vec<LinkEntry> {
+    let arena = Arena::new();
+    let options = comrak_options();
+    let root = parse_document(&arena, text, &options);
+
+    let mut links = Vec::new();
+    for node in root.descendants() {
+        if let NodeValue::Link(link) = &node.data.borrow().value {
+            links.push(LinkEntry {
+                internal: is_internal_link(&link.url),
+                rewritten: rewrite_link_url(&link.url),
+                original: link.url.clone(),
+            });
+        }
+    }
+    links
+}
+
+fn block_type_of(lines: &[String]) -> Option<&'static str> {
+    for line in lines {
+        let stripped = line.trim();
+        if stripped.is_empty() {
+            continue;
+        }
+        if stripped.starts_with("flowchart") || stripped.starts_with("graph") {
+            return Some("flowchart");
+        } else if stripped.starts_with("sequenceDiagram") {
+            return Some("sequence");
+        }
+        break;
+    }
+    None
+}
+
+fn extract_mermaid_graphs(text: &str) -> Vec<MermaidGraph> {
+    extract_mermaid_blocks(text)
+        .into_iter()
+        .filter_map(|raw_lines| {
+            let lines = fix_malformed_nodes(&sanitize_node_labels(&raw_lines));
+            match block_type_of(&lines) {
+                Some("flowchart") => {
+                    let (edges, node_labels): (Vec<crate::Edge>, HashMap<String, String>) =
+                        parse_flowchart_graph(&lines);
+                    let mut node_ids: Vec<String> = Vec::new();
+                    for edge in &edges {
+                        for id in [&edge.src, &edge.dst] {
+                            if !node_ids.contains(id) {
+                                node_ids.push(id.clone());
+                            }
+                        }
+                    }
+                    let nodes = node_ids
+                        .into_iter()
+                        .map(|id| {
+                            let label = node_labels.get(&id).cloned();
+                            FlowchartNode { id, label }
+                        })
+                        .collect();
+                    let edges = edges
+                        .into_iter()
+                        .map(|e| Edge {
+                            src: e.src,
+                            dst: e.dst,
+                            arrow: e.arrow,
+                            label: e.label,
+                        })
+                        .collect();
+                    Some(MermaidGraph::Flowchart { nodes, edges })
+                }
+                Some("sequence") => {
+                    let aliases = parse_sequence_participant_aliases(&lines);
+                    let participants = aliases
+                        .into_iter()
+                        .map(|(name, alias)| ParticipantAlias { name, alias })
+                        .collect();
+                    Some(MermaidGraph::Sequence { participants })
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+pub fn build_document_model(rel_path: &str, text: &str) -> DocumentModel {
+    DocumentModel {
+        path: rel_path.to_string(),
+        headings: extract_headings(text),
+        links: extract_links(text),
+        mermaid: extract_mermaid_graphs(text),
+    }
+}
+
+/// Walk every markdown file under `output_dir`, emit its JSON model as a
+/// mirrored `.json` file under `json_dir`, and return the paths written.
+pub fn process_directory_to_json(output_dir: &Path, json_dir: &Path) -> Vec<std::path::PathBuf> {
+    let mut written = Vec::new();
+
+    for entry in WalkDir::new(output_dir).into_iter().filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if !is_markdown(path) {
+            continue;
+        }
+
+        let rel_path = path.strip_prefix(output_dir).unwrap_or(path);
+        let mut out_path = json_dir.join(rel_path);
+        out_path.set_extension("json");
+
+        let content = match fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
+
+        let model = build_document_model(&rel_path.to_string_lossy(), &content);
+        let json = match serde_json::to_string_pretty(&model) {
+            Ok(j) => j,
+            Err(_) => continue,
+        };
+
+        if let Some(parent) = out_path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if fs::write(&out_path, json).is_ok() {
+            written.push(out_path);
+        }
+    }
+
+    written
+}
@@ -0,0 +1,55 @@
+//! Line-ending and trailing-whitespace normalization for the
+//! `process_text` stage: DeepWiki exports frequently mix CRLF and LF,
+//! which `read_to_string`/`write` otherwise pass through unchanged,
+//! breaking downstream pandoc and producing noisy diffs. Modeled on
+//! pkgcheck's `recode` module and its `wrong_line_endings2lf`/`2crlf`
+//! helpers.
+
+use clap::ValueEnum;
+
+/// Target line ending for `--eol`, defaulting to `Lf`.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Eol {
+    Lf,
+    Crlf,
+}
+
+impl Eol {
+    fn as_str(self) -> &'static str {
+        match self {
+            Eol::Lf => "\n",
+            Eol::Crlf => "\r\n",
+        }
+    }
+}
+
+/// Convert every line ending in `text` to `target` and strip trailing
+/// spaces from each line, except inside fenced code blocks (whose
+/// contents shouldn't be touched) and lines ending in a Markdown hard
+/// break (two trailing spaces), which trailing-whitespace stripping
+/// would otherwise destroy.
+pub fn normalize(text: &str, target: Eol) -> String {
+    let ending = target.as_str();
+    let lines: Vec<&str> = text.lines().collect();
+    let trailing_newline = text.ends_with('\n');
+
+    let mut result = String::with_capacity(text.len());
+    let mut in_fence = false;
+
+    for (idx, line) in lines.iter().enumerate() {
+        if line.trim_start().starts_with("```") || line.trim_start().starts_with("~~~") {
+            in_fence = !in_fence;
+            result.push_str(line);
+        } else if in_fence || line.ends_with("  ") {
+            result.push_str(line);
+        } else {
+            result.push_str(line.trim_end());
+        }
+
+        if idx + 1 < lines.len() || trailing_newline {
+            result.push_str(ending);
+        }
+    }
+
+    result
+}
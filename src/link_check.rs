@@ -0,0 +1,108 @@
+//! Internal link validation for `--check-links`.
+//!
+//! `rewrite_markdown_links` rewrites relative markdown links during
+//! `apply_readme_ordinal`'s ordinal renaming, but nothing afterwards
+//! confirms the result actually resolves, so a mistyped or dropped
+//! mapping entry silently produces a dangling link. This walks
+//! `output_dir`, resolves every relative link the same way the rewriter
+//! does, and flags any target file (or `#anchor`) that doesn't exist.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use walkdir::WalkDir;
+
+use crate::is_markdown;
+use crate::slug::slugify;
+
+pub struct BrokenLink {
+    pub file: PathBuf,
+    pub target: String,
+    pub reason: String,
+}
+
+/// Parse a target file's `#`/`##` headings into GitHub-style slugs, the
+/// same flavor `rewrite_markdown_links`'s mapping targets are built from.
+fn heading_slugs(path: &Path) -> Vec<String> {
+    let text = match fs::read_to_string(path) {
+        Ok(t) => t,
+        Err(_) => return Vec::new(),
+    };
+    let heading_re = Regex::new(r"^(#{1,2})\s+(.+?)\s*$").unwrap();
+    text.lines()
+        .filter_map(|line| heading_re.captures(line))
+        .map(|caps| slugify(&caps[2]))
+        .collect()
+}
+
+/// Walk `output_dir` and report every link whose target file, or whose
+/// `#anchor` in another file, does not resolve. `http(s)://` links and
+/// same-file `#anchor` links are not checked.
+pub fn check_links(output_dir: &Path) -> Vec<BrokenLink> {
+    let link_re = Regex::new(r"\]\(([^)]+)\)").unwrap();
+    let mut broken = Vec::new();
+
+    for entry in WalkDir::new(output_dir).into_iter().filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if !is_markdown(path) {
+            continue;
+        }
+        let text = match fs::read_to_string(path) {
+            Ok(t) => t,
+            Err(_) => continue,
+        };
+        let base_dir = path.parent().unwrap_or(output_dir);
+
+        for caps in link_re.captures_iter(&text) {
+            let target = &caps[1];
+            if target.starts_with("http://") || target.starts_with("https://") || target.starts_with('#') {
+                continue;
+            }
+
+            let (target_part, anchor) = match target.find('#') {
+                Some(pos) => (&target[..pos], Some(&target[pos + 1..])),
+                None => (target, None),
+            };
+            if target_part.is_empty() {
+                continue;
+            }
+
+            // Resolve relative to the containing file's directory;
+            // `Path::join` keeps any `../`/`./` components and the
+            // filesystem resolves them in `exists()`.
+            let resolved = base_dir.join(target_part);
+            if !resolved.exists() {
+                broken.push(BrokenLink {
+                    file: path.to_path_buf(),
+                    target: target.to_string(),
+                    reason: format!("target file '{}' does not exist", resolved.display()),
+                });
+                continue;
+            }
+
+            if let Some(anchor) = anchor {
+                if !anchor.is_empty() && !heading_slugs(&resolved).iter().any(|slug| slug == anchor) {
+                    broken.push(BrokenLink {
+                        file: path.to_path_buf(),
+                        target: target.to_string(),
+                        reason: format!(
+                            "no heading matches anchor '#{}' in '{}'",
+                            anchor,
+                            resolved.display()
+                        ),
+                    });
+                }
+            }
+        }
+    }
+
+    broken
+}
+
+/// Print every broken link to stderr, one per line.
+pub fn report(broken: &[BrokenLink]) {
+    for link in broken {
+        eprintln!("{}: [{}] {}", link.file.display(), link.target, link.reason);
+    }
+}
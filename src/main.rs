@@ -1,4 +1,7 @@
 use clap::Parser;
+use comrak::nodes::NodeValue;
+use comrak::{parse_document, Arena, ComrakOptions};
+use rayon::prelude::*;
 use regex::Regex;
 use std::collections::HashMap;
 use std::fs;
@@ -6,6 +9,18 @@ use std::path::{Path, PathBuf};
 use std::process::Command;
 use walkdir::WalkDir;
 
+mod ast_pipeline;
+mod config;
+mod dedup;
+mod heading_text;
+mod html_render;
+mod json_model;
+mod line_endings;
+mod link_check;
+mod mdbook_gen;
+mod preprocessor;
+mod slug;
+
 #[derive(Parser, Debug)]
 #[command(name = "fix-docs")]
 #[command(about = "Normalize DeepWiki markdown links and mermaid diagrams.")]
@@ -33,20 +48,41 @@ struct Args {
     /// Output directory for PDF files (default: ./output-pdf)
     #[arg(long, default_value = "./output-pdf")]
     pdf_dir: PathBuf,
-}
 
-// Section anchors mapping
-fn get_section_anchors() -> HashMap<&'static str, &'static str> {
-    let mut map = HashMap::new();
-    map.insert("Networking Section", "networking-configuration");
-    map.insert("Virtual Environment Section", "virtual-environment-setup");
-    map.insert("Module Import Section", "module-import-issues");
-    map.insert("WSL.exe Section", "wslexe-issues");
-    map.insert("Path Translation Section", "path-translation-issues");
-    map.insert("Performance Section", "performance-optimization");
-    map.insert("Line Ending Section", "line-ending-issues");
-    map.insert("Distribution Section", "distribution-selection");
-    map
+    /// Render output documents as standalone HTML (requires output folder to exist)
+    #[arg(long)]
+    html: bool,
+
+    /// Output directory for HTML files (default: ./output-html)
+    #[arg(long, default_value = "./output-html")]
+    html_dir: PathBuf,
+
+    /// Emit a structured JSON model (outline, link graph, mermaid graphs)
+    /// of each document into this directory (requires output folder to exist)
+    #[arg(long)]
+    json: Option<PathBuf>,
+
+    /// Validate that internal markdown links resolve, exiting non-zero if
+    /// any are broken (requires output folder to exist)
+    #[arg(long)]
+    check_links: bool,
+
+    /// Number of worker threads for parallel file/PDF processing (default: all cores)
+    #[arg(long)]
+    jobs: Option<usize>,
+
+    /// Generate an mdBook project (SUMMARY.md + book.toml) for each output
+    /// project directory (requires output folder to exist)
+    #[arg(long)]
+    mdbook: bool,
+
+    /// Path to a deepwiki.toml config file overriding PDF rendering defaults
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Target line ending for normalized markdown output
+    #[arg(long, value_enum, default_value = "lf")]
+    eol: line_endings::Eol,
 }
 
 const BRANCH_LABELS: &[&str] = &["yes", "no", "true", "false"];
@@ -60,54 +96,13 @@ const NEGATIVE_HINTS: &[&str] = &[
 ];
 
 #[derive(Clone)]
-struct Edge {
+pub(crate) struct Edge {
     line_idx: usize,
-    indent: String,
-    src: String,
-    arrow: String,
-    label: Option<String>,
-    dst: String,
-}
-
-fn fix_internal_links(text: &str) -> String {
-    let internal_link_re = Regex::new(r"\]\((/[^)/\s]+/[^)/\s]+(?:/[^\s)]*)?)\)").unwrap();
-    let ref_style_re = Regex::new(r"(?m)(^\s*\[[^\]]+\]:\s*)(/[^)/\s]+/[^)/\s]+(?:/[^\s)]*)?)").unwrap();
-    
-    let text = internal_link_re.replace_all(text, |caps: &regex::Captures| {
-        format!("](https://github.com{})", &caps[1])
-    });
-    
-    let text = ref_style_re.replace_all(&text, |caps: &regex::Captures| {
-        format!("{}https://github.com{}", &caps[1], &caps[2])
-    });
-    
-    text.to_string()
-}
-
-fn fix_section_links(text: &str) -> String {
-    let section_anchors = get_section_anchors();
-    let mut result = text.to_string();
-    
-    for (section, anchor) in section_anchors {
-        let pattern = format!(
-            r"https://github\.com/(?P<owner>[^/]+)/(?P<repo>[^/]+)/blob/(?P<sha>[0-9a-f]{{7,40}})/{}",
-            regex::escape(section)
-        );
-        let re = Regex::new(&pattern).unwrap();
-        result = re.replace_all(&result, |caps: &regex::Captures| {
-            format!(
-                "https://github.com/{}/{}/blob/{}/README.md#{}",
-                &caps["owner"], &caps["repo"], &caps["sha"], anchor
-            )
-        }).to_string();
-    }
-    
-    result
-}
-
-fn strip_github_blob_sha(text: &str) -> String {
-    let re = Regex::new(r"https://github\.com/([^/]+)/([^/]+)/blob/([0-9a-f]{7,40})/").unwrap();
-    re.replace_all(text, "https://github.com/$1/$2/").to_string()
+    pub(crate) indent: String,
+    pub(crate) src: String,
+    pub(crate) arrow: String,
+    pub(crate) label: Option<String>,
+    pub(crate) dst: String,
 }
 
 fn strip_preamble(text: &str) -> String {
@@ -182,12 +177,13 @@ fn sanitize_label(label: &str) -> String {
     result.to_string()
 }
 
-fn fix_sequence_diagram_participants(lines: &[String]) -> Vec<String> {
+/// Identify sequence-diagram participants whose names need a safe alias
+/// (i.e. they contain spaces or hyphens), mapping original name -> alias.
+/// Shared by `fix_sequence_diagram_participants` and `json_model`.
+pub(crate) fn parse_sequence_participant_aliases(lines: &[String]) -> HashMap<String, String> {
     let participant_re = Regex::new(r"^\s*(participant)\s+(.+)$").unwrap();
     let mut participant_aliases: HashMap<String, String> = HashMap::new();
-    let mut result: Vec<String> = Vec::new();
-    
-    // First pass: identify participants with spaces and create aliases
+
     for line in lines {
         if let Some(caps) = participant_re.captures(line) {
             let original_name = caps.get(2).unwrap().as_str().trim();
@@ -200,7 +196,15 @@ fn fix_sequence_diagram_participants(lines: &[String]) -> Vec<String> {
             }
         }
     }
-    
+
+    participant_aliases
+}
+
+fn fix_sequence_diagram_participants(lines: &[String]) -> Vec<String> {
+    let participant_re = Regex::new(r"^\s*(participant)\s+(.+)$").unwrap();
+    let participant_aliases = parse_sequence_participant_aliases(lines);
+    let mut result: Vec<String> = Vec::new();
+
     // Second pass: replace in all lines
     for line in lines {
         let mut new_line = line.clone();
@@ -224,7 +228,7 @@ fn fix_sequence_diagram_participants(lines: &[String]) -> Vec<String> {
     result
 }
 
-fn fix_malformed_nodes(lines: &[String]) -> Vec<String> {
+pub(crate) fn fix_malformed_nodes(lines: &[String]) -> Vec<String> {
     // Fix patterns like INPUTENC[broken-content]"] - specific to broken DeepWiki exports
     let broken_re = Regex::new(r#"(\w+)\[broken-content\]\"\]"#).unwrap();
     
@@ -240,7 +244,7 @@ fn fix_malformed_nodes(lines: &[String]) -> Vec<String> {
         .collect()
 }
 
-fn sanitize_node_labels(lines: &[String]) -> Vec<String> {
+pub(crate) fn sanitize_node_labels(lines: &[String]) -> Vec<String> {
     let node_label_re = Regex::new(r#"\["(.*?)"\]"#).unwrap();
     lines
         .iter()
@@ -341,15 +345,18 @@ fn choose_edge(
     if winners.len() == 1 { Some(winners[0]) } else { None }
 }
 
-fn move_branch_labels(lines: &mut Vec<String>) {
+/// Parse a flowchart's node labels and edges out of its fenced-block lines.
+/// Shared by `move_branch_labels` (which mutates the edges back into text)
+/// and `json_model` (which serializes them as-is for `--json`).
+pub(crate) fn parse_flowchart_graph(lines: &[String]) -> (Vec<Edge>, HashMap<String, String>) {
     let edge_re = Regex::new(
         r#"^(?P<indent>\s*)(?P<src>[A-Za-z0-9_]+)\s*(?P<arrow>[-.=]+>)\s*(?:\|"(?P<label>[^"]*)"\|\s*)?(?P<dst>[A-Za-z0-9_]+)\s*$"#
     ).unwrap();
     let node_label_re = Regex::new(r#"\["(.*?)"\]"#).unwrap();
-    
+
     let mut edges: Vec<Edge> = Vec::new();
     let mut node_labels: HashMap<String, String> = HashMap::new();
-    
+
     for (idx, line) in lines.iter().enumerate() {
         if let Some(label_match) = node_label_re.captures(line) {
             let label = label_match.get(1).unwrap().as_str();
@@ -358,7 +365,7 @@ fn move_branch_labels(lines: &mut Vec<String>) {
                 node_labels.insert(prefix.to_string(), label.to_string());
             }
         }
-        
+
         if let Some(caps) = edge_re.captures(line) {
             edges.push(Edge {
                 line_idx: idx,
@@ -370,7 +377,13 @@ fn move_branch_labels(lines: &mut Vec<String>) {
             });
         }
     }
-    
+
+    (edges, node_labels)
+}
+
+fn move_branch_labels(lines: &mut Vec<String>) {
+    let (mut edges, node_labels) = parse_flowchart_graph(lines);
+
     let mut outgoing: HashMap<String, Vec<usize>> = HashMap::new();
     for (edge_idx, edge) in edges.iter().enumerate() {
         outgoing.entry(edge.src.clone()).or_default().push(edge_idx);
@@ -491,192 +504,45 @@ fn sanitize_mermaid(text: &str) -> String {
     result
 }
 
-fn fix_table_content(text: &str) -> String {
-    let mut result = String::new();
-    let mut in_table = false;
-    let mut table_rows: Vec<String> = Vec::new();
-    
-    for line in text.lines() {
-        // Detectar tabelas markdown
-        if line.trim().starts_with('|') {
-            if !in_table {
-                in_table = true;
-                table_rows.clear();
-            }
-            table_rows.push(line.to_string());
-            continue;
-        } else if in_table && (line.trim().is_empty() || line.trim().starts_with('|')) {
-            if line.trim().starts_with('|') {
-                table_rows.push(line.to_string());
-                continue;
-            } else {
-                // Fim da tabela
-                in_table = false;
-                let fixed_table = fix_table_rows(&table_rows);
-                result.push_str(&fixed_table);
-                result.push('\n');
-                table_rows.clear();
-            }
-        } else if in_table {
-            // Fim da tabela
-            in_table = false;
-            let fixed_table = fix_table_rows(&table_rows);
-            result.push_str(&fixed_table);
-            result.push('\n');
-            table_rows.clear();
-        }
-        
-        if !in_table {
-            result.push_str(line);
-            result.push('\n');
-        }
-    }
-    
-    // Se terminou com tabela aberta
-    if in_table && !table_rows.is_empty() {
-        let fixed_table = fix_table_rows(&table_rows);
-        result.push_str(&fixed_table);
-    }
-    
-    result
-}
-
-fn fix_table_rows(rows: &[String]) -> String {
-    let mut result = String::new();
-    
-    for row in rows {
-        if row.trim().starts_with('|') {
-            let columns: Vec<&str> = row.split('|').collect();
-            let mut fixed_columns: Vec<String> = Vec::new();
-            
-            for (i, col) in columns.iter().enumerate() {
-                if i == 0 || i == columns.len() - 1 {
-                    // Primeira e última coluna são vazias (antes/after do |)
-                    continue;
-                }
-                
-                let col_content = col.trim();
-                
-                // Quebrar colunas muito longas
-                if col_content.len() > 30 {
-                    let words: Vec<&str> = col_content.split_whitespace().collect();
-                    let mut current_line = String::new();
-                    let mut lines = Vec::new();
-                    
-                    for word in words {
-                        if current_line.is_empty() {
-                            current_line.push_str(word);
-                        } else if current_line.len() + 1 + word.len() <= 30 {
-                            current_line.push(' ');
-                            current_line.push_str(word);
-                        } else {
-                            lines.push(current_line);
-                            current_line = word.to_string();
-                        }
-                    }
-                    
-                    if !current_line.is_empty() {
-                        lines.push(current_line);
-                    }
-                    
-                    // Juntar linhas com <br> para quebra no PDF
-                    fixed_columns.push(lines.join("<br>"));
-                } else {
-                    fixed_columns.push(col_content.to_string());
-                }
-            }
-            
-            // Reconstruir linha da tabela
-            result.push_str("|");
-            for col in &fixed_columns {
-                result.push(' ');
-                result.push_str(col);
-                result.push_str(" |");
-            }
-            result.push('\n');
-        } else {
-            // Linha de separação (|---|---|)
-            result.push_str(row);
-            result.push('\n');
-        }
-    }
-    
-    result
-}
+/// Collect the raw lines of every ` ```mermaid ` fenced block in `text`,
+/// in document order. Shared by `json_model` so it can build the same
+/// graph model `sanitize_mermaid` reconstructs internally, without
+/// rewriting the document.
+pub(crate) fn extract_mermaid_blocks(text: &str) -> Vec<Vec<String>> {
+    let mut blocks: Vec<Vec<String>> = Vec::new();
+    let mut in_block = false;
+    let mut block_lines: Vec<String> = Vec::new();
 
-fn fix_long_lines(text: &str) -> String {
-    let mut result = String::new();
-    let max_line_length = 80; // Limite de caracteres por linha
-    let mut in_code_block = false;
-    
     for line in text.lines() {
-        // Detectar início/fim de blocos de código
-        if line.trim().starts_with("```") {
-            in_code_block = !in_code_block;
-            result.push_str(line);
-            result.push('\n');
+        if line.starts_with("```") && line.contains("mermaid") {
+            in_block = true;
+            block_lines.clear();
             continue;
         }
-        
-        // Para blocos de código, quebrar linhas muito longas
-        if in_code_block && line.len() > max_line_length {
-            // Para código, quebrar em pontos lógicos ou simplesmente no limite
-            if line.len() > max_line_length * 2 {
-                // Linhas extremamente longas - quebrar no limite
-                for (i, chunk) in line.as_bytes().chunks(max_line_length).enumerate() {
-                    if i > 0 {
-                        result.push('\n');
-                    }
-                    result.push_str(&String::from_utf8_lossy(chunk));
-                }
-                result.push('\n');
+        if in_block {
+            if line.starts_with("```") {
+                blocks.push(std::mem::take(&mut block_lines));
+                in_block = false;
             } else {
-                result.push_str(line);
-                result.push('\n');
-            }
-        } else if !in_code_block && line.len() > max_line_length {
-            // Texto normal - quebrar em palavras
-            let words: Vec<&str> = line.split_whitespace().collect();
-            let mut current_line = String::new();
-            
-            for word in words {
-                if current_line.is_empty() {
-                    current_line.push_str(word);
-                } else if current_line.len() + 1 + word.len() <= max_line_length {
-                    current_line.push(' ');
-                    current_line.push_str(word);
-                } else {
-                    result.push_str(&current_line);
-                    result.push('\n');
-                    current_line = word.to_string();
-                }
-            }
-            
-            if !current_line.is_empty() {
-                result.push_str(&current_line);
-                result.push('\n');
+                block_lines.push(line.to_string());
             }
-        } else {
-            result.push_str(line);
-            result.push('\n');
         }
     }
-    
-    result
+    if in_block {
+        blocks.push(block_lines);
+    }
+
+    blocks
 }
 
-fn process_text(text: &str) -> String {
+fn process_text(text: &str, eol: line_endings::Eol) -> String {
     let text = strip_preamble(text);
     let text = remove_link_copied(&text);
     let text = remove_ask_devin_lines(&text);
     let text = fix_literal_backslash_n(&text);
-    let text = fix_internal_links(&text);
-    let text = fix_section_links(&text);
-    let text = strip_github_blob_sha(&text);
     let text = sanitize_mermaid(&text);
-    let text = fix_table_content(&text);
-    let text = fix_long_lines(&text);
-    text
+    let text = ast_pipeline::process_with_ast(&text);
+    line_endings::normalize(&text, eol)
 }
 
 fn parse_readme_index(readme_path: &Path) -> Vec<String> {
@@ -743,7 +609,89 @@ fn build_ordinal_mapping(readme_path: &Path) -> HashMap<String, String> {
     mapping
 }
 
-fn rewrite_markdown_links(text: &str, mapping: &HashMap<String, String>) -> String {
+/// Parse the first `Heading` node in a document and collect its rendered
+/// text, so a title like `# The [API](...) Guide` comes out as `The API
+/// Guide` rather than being mangled by a `starts_with('#')` line slice.
+fn first_heading_title(text: &str) -> Option<String> {
+    let arena = Arena::new();
+    let options = ComrakOptions::default();
+    let root = parse_document(&arena, text, &options);
+
+    for node in root.descendants() {
+        if matches!(node.data.borrow().value, NodeValue::Heading(_)) {
+            let title = heading_text::collect_text(node);
+            if !title.is_empty() {
+                return Some(title);
+            }
+        }
+    }
+    None
+}
+
+/// Fall back to a title-derived ordinal/slug for markdown files the
+/// README index doesn't cover (missing README, or a README whose bullet
+/// list omits some files), continuing the ordinal sequence after the
+/// highest ordinal already in use, whether from `existing`'s rewritten
+/// names or from files on disk that were already numbered.
+fn build_title_mapping(dir: &Path, existing: &HashMap<String, String>) -> HashMap<String, String> {
+    let numbered_re = Regex::new(r"^(\d{2})-").unwrap();
+    let mut mapping = HashMap::new();
+
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| {
+            is_markdown(p)
+                && p.file_name().map_or(false, |f| f != "README.md")
+        })
+        .collect();
+    entries.sort();
+
+    // `existing` only holds rewrite targets for README items that weren't
+    // already numbered, so its length under-counts ordinals already in
+    // use. Parse the actual `NN-` prefix out of every rewritten name
+    // *and* every already-numbered file still on disk to find the true
+    // highest ordinal in use before continuing the sequence.
+    let ordinal_of = |name: &str| -> Option<usize> {
+        numbered_re.captures(name).and_then(|caps| caps[1].parse().ok())
+    };
+    let mut next_ordinal = existing
+        .values()
+        .filter_map(|name| ordinal_of(name))
+        .chain(
+            entries
+                .iter()
+                .filter_map(|p| p.file_name().and_then(|f| f.to_str()).and_then(ordinal_of)),
+        )
+        .max()
+        .unwrap_or(0)
+        + 1;
+
+    for path in entries {
+        let filename = match path.file_name().and_then(|f| f.to_str()) {
+            Some(f) => f,
+            None => continue,
+        };
+        if existing.contains_key(filename) || numbered_re.is_match(filename) {
+            continue;
+        }
+
+        let title = match fs::read_to_string(&path).ok().and_then(|t| first_heading_title(&t)) {
+            Some(t) => t,
+            None => continue,
+        };
+
+        let new_name = format!("{:02}-{}.md", next_ordinal, slug::slugify(&title));
+        next_ordinal += 1;
+        mapping.insert(filename.to_string(), new_name);
+    }
+
+    mapping
+}
+
+pub(crate) fn rewrite_markdown_links(text: &str, mapping: &HashMap<String, String>) -> String {
     let link_re = Regex::new(r"\]\(([^)]+)\)").unwrap();
     
     link_re.replace_all(text, |caps: &regex::Captures| {
@@ -779,21 +727,26 @@ fn rewrite_markdown_links(text: &str, mapping: &HashMap<String, String>) -> Stri
     }).to_string()
 }
 
-fn apply_readme_ordinal(output_dir: &Path, dry_run: bool) -> Vec<PathBuf> {
+fn apply_readme_ordinal(output_dir: &Path, dry_run: bool, eol: line_endings::Eol) -> Vec<PathBuf> {
     let readme_path = output_dir.join("README.md");
-    if !readme_path.exists() {
-        return Vec::new();
-    }
-    
-    // Clean up the README first to fix broken links
-    if let Ok(original_readme) = fs::read_to_string(&readme_path) {
-        let cleaned_readme = process_text(&original_readme);
-        if cleaned_readme != original_readme && !dry_run {
-            let _ = fs::write(&readme_path, &cleaned_readme);
+    let mut mapping = HashMap::new();
+
+    if readme_path.exists() {
+        // Clean up the README first to fix broken links
+        if let Ok(original_readme) = fs::read_to_string(&readme_path) {
+            let cleaned_readme = process_text(&original_readme, eol);
+            if cleaned_readme != original_readme && !dry_run {
+                let _ = fs::write(&readme_path, &cleaned_readme);
+            }
         }
+
+        mapping = build_ordinal_mapping(&readme_path);
     }
-    
-    let mapping = build_ordinal_mapping(&readme_path);
+
+    // README missing entirely, or its bullet list doesn't cover every
+    // file: derive ordinals/slugs from each remaining file's own title.
+    mapping.extend(build_title_mapping(output_dir, &mapping));
+
     if mapping.is_empty() {
         return Vec::new();
     }
@@ -803,7 +756,7 @@ fn apply_readme_ordinal(output_dir: &Path, dry_run: bool) -> Vec<PathBuf> {
     // Update links in all markdown files
     for entry in WalkDir::new(output_dir).into_iter().filter_map(|e| e.ok()) {
         let path = entry.path();
-        if path.extension().map_or(false, |e| e == "md") {
+        if is_markdown(path) {
             if let Ok(original) = fs::read_to_string(path) {
                 let updated = rewrite_markdown_links(&original, &mapping);
                 if updated != original {
@@ -838,61 +791,99 @@ fn apply_readme_ordinal(output_dir: &Path, dry_run: bool) -> Vec<PathBuf> {
     changed
 }
 
-fn process_directory(input_dir: &Path, output_dir: &Path, dry_run: bool) -> Vec<PathBuf> {
-    let mut changed_files: Vec<PathBuf> = Vec::new();
-    
-    for entry in WalkDir::new(input_dir).into_iter().filter_map(|e| e.ok()) {
-        let path = entry.path();
-        if path.is_dir() {
-            continue;
-        }
-        
-        let rel_path = path.strip_prefix(input_dir).unwrap_or(path);
-        if rel_path.file_name()
-            .and_then(|f| f.to_str())
-            .map_or(false, |f| f.starts_with('.'))
-        {
-            continue;
-        }
-        
-        let out_path = output_dir.join(rel_path);
-        
-        if path.extension().map_or(true, |e| e != "md") {
-            // Copy non-markdown files
-            if !dry_run {
-                if let Some(parent) = out_path.parent() {
-                    let _ = fs::create_dir_all(parent);
-                }
-                let _ = fs::copy(path, &out_path);
+/// Copy (or process, if markdown) a single file from `input_dir` into its
+/// mirrored location under `output_dir`, returning the output path if
+/// markdown processing changed its contents. Split out of
+/// `process_directory` so the per-file work can run on a rayon parallel
+/// iterator.
+fn process_single_file(
+    path: &Path,
+    input_dir: &Path,
+    output_dir: &Path,
+    dry_run: bool,
+    eol: line_endings::Eol,
+) -> Option<PathBuf> {
+    let rel_path = path.strip_prefix(input_dir).unwrap_or(path);
+    if rel_path
+        .file_name()
+        .and_then(|f| f.to_str())
+        .map_or(false, |f| f.starts_with('.'))
+    {
+        return None;
+    }
+
+    let out_path = output_dir.join(rel_path);
+
+    if !is_markdown(path) {
+        // Copy non-markdown files
+        if !dry_run {
+            if let Some(parent) = out_path.parent() {
+                let _ = fs::create_dir_all(parent);
             }
-            continue;
+            let _ = fs::copy(path, &out_path);
         }
-        
-        // Process markdown files
-        if let Ok(original) = fs::read_to_string(path) {
-            let updated = process_text(&original);
-            if updated != original {
-                changed_files.push(out_path.clone());
-            }
-            if !dry_run {
-                if let Some(parent) = out_path.parent() {
-                    let _ = fs::create_dir_all(parent);
-                }
-                let _ = fs::write(&out_path, &updated);
-            }
+        return None;
+    }
+
+    // Process markdown files
+    let original = fs::read_to_string(path).ok()?;
+    let updated = process_text(&original, eol);
+    let changed = updated != original;
+
+    if !dry_run {
+        if let Some(parent) = out_path.parent() {
+            let _ = fs::create_dir_all(parent);
         }
+        let _ = fs::write(&out_path, &updated);
     }
-    
-    // Apply ordinal renaming to each subdirectory that has a README.md
+
+    changed.then_some(out_path)
+}
+
+fn process_directory(
+    input_dir: &Path,
+    output_dir: &Path,
+    dry_run: bool,
+    eol: line_endings::Eol,
+) -> Vec<PathBuf> {
+    let entries: Vec<PathBuf> = WalkDir::new(input_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path().to_path_buf())
+        .filter(|path| !path.is_dir())
+        .collect();
+
+    // Reads and writes are independent per file, so run them concurrently;
+    // only the rename phase below needs to stay serial.
+    let mut changed_files: Vec<PathBuf> = entries
+        .into_par_iter()
+        .filter_map(|path| process_single_file(&path, input_dir, output_dir, dry_run, eol))
+        .collect();
+
+    // Directories without a README.md still get title-derived ordinals
+    // through `apply_readme_ordinal`'s fallback.
+    let dirs_without_readme: Vec<PathBuf> = WalkDir::new(output_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_dir() && !e.path().join("README.md").is_file())
+        .map(|e| e.path().to_path_buf())
+        .collect();
+    for dir in dirs_without_readme {
+        changed_files.extend(apply_readme_ordinal(&dir, dry_run, eol));
+    }
+
+    // Apply ordinal renaming to each subdirectory that has a README.md.
+    // Renames mutate shared paths, so this phase stays serial.
     for entry in WalkDir::new(output_dir).into_iter().filter_map(|e| e.ok()) {
         let path = entry.path();
         if path.is_file() && path.file_name().map_or(false, |f| f == "README.md") {
             if let Some(parent) = path.parent() {
-                changed_files.extend(apply_readme_ordinal(parent, dry_run));
+                changed_files.extend(apply_readme_ordinal(parent, dry_run, eol));
+                changed_files.extend(dedup::dedup_directory(parent, dry_run));
             }
         }
     }
-    
+
     changed_files
 }
 
@@ -1000,15 +991,21 @@ fn process_mermaid_for_pdf(text: &str, images_dir: &Path, prefix: &str) -> Strin
     result
 }
 
+/// Whether `path` has a `.md` extension. Shared by every pass that needs
+/// to tell markdown files apart from the rest of a project directory.
+pub(crate) fn is_markdown(path: &Path) -> bool {
+    path.extension().map_or(false, |e| e == "md")
+}
+
 /// Get sorted markdown files from a project directory (excluding README.md)
-fn get_sorted_markdown_files(project_dir: &Path) -> Vec<PathBuf> {
+pub(crate) fn get_sorted_markdown_files(project_dir: &Path) -> Vec<PathBuf> {
     let mut files: Vec<PathBuf> = fs::read_dir(project_dir)
         .into_iter()
         .flatten()
         .filter_map(|e| e.ok())
         .map(|e| e.path())
         .filter(|p| {
-            p.extension().map_or(false, |e| e == "md") &&
+            is_markdown(p) &&
             p.file_name().map_or(false, |f| f != "README.md")
         })
         .collect();
@@ -1067,17 +1064,38 @@ fn consolidate_project_markdown(project_dir: &Path, images_dir: &Path) -> String
 }
 
 /// Convert a project directory to a single PDF using pandoc
-fn convert_project_to_pdf(project_dir: &Path, pdf_dir: &Path) -> Result<PathBuf, String> {
+fn convert_project_to_pdf(project_dir: &Path, pdf_dir: &Path, deepwiki_config: &config::Config) -> Result<PathBuf, String> {
     let project_name = project_dir.file_name()
         .and_then(|f| f.to_str())
         .ok_or("Invalid project directory name")?;
+    let pdf_config = &deepwiki_config.pdf;
 
     // Create temporary directory for images and markdown
     let temp_dir = tempfile::tempdir().map_err(|e| e.to_string())?;
     let images_dir = temp_dir.path();
 
+    // Let any declared `[[preprocessor]]` commands transform the file set
+    // before consolidation, unless none are configured or none opt in.
+    let consolidation_dir = if deepwiki_config.preprocessor.is_empty() {
+        None
+    } else {
+        let documents = preprocessor::collect_project_documents(project_dir);
+        let transformed = preprocessor::run_all(&deepwiki_config.preprocessor, documents);
+
+        let preprocessed_dir = temp_dir.path().join("preprocessed");
+        for doc in &transformed {
+            let out_path = preprocessed_dir.join(&doc.path);
+            if let Some(parent) = out_path.parent() {
+                fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            fs::write(&out_path, &doc.content).map_err(|e| e.to_string())?;
+        }
+        Some(preprocessed_dir)
+    };
+    let project_dir_for_consolidation = consolidation_dir.as_deref().unwrap_or(project_dir);
+
     // Consolidate all markdown files
-    let consolidated = consolidate_project_markdown(project_dir, images_dir);
+    let consolidated = consolidate_project_markdown(project_dir_for_consolidation, images_dir);
 
     // Write consolidated markdown to temp file
     let md_path = temp_dir.path().join("consolidated.md");
@@ -1086,35 +1104,48 @@ fn convert_project_to_pdf(project_dir: &Path, pdf_dir: &Path) -> Result<PathBuf,
     // Create output PDF path
     let pdf_path = pdf_dir.join(format!("{}.pdf", project_name));
 
-    // Write custom title page template
+    // Write the title page template, either the user's own (with
+    // `{title}` substituted) or the crate's built-in default.
     let title_path = temp_dir.path().join("title.tex");
-    let title_template = format!(r#"\begin{{titlepage}}
+    let escaped_name = project_name.replace('_', r"\_").replace('-', r"-");
+    let title_template = match &pdf_config.title.template {
+        Some(template) => template.replace("{title}", &escaped_name),
+        None => format!(r#"\begin{{titlepage}}
 \centering
 \vspace*{{3cm}}
 {{\fontsize{{32}}{{40}}\selectfont\bfseries {} \par}}
 \vfill
 \end{{titlepage}}
-"#, project_name.replace('_', r"\_").replace('-', r"-"));
+"#, escaped_name),
+    };
     fs::write(&title_path, &title_template).map_err(|e| e.to_string())?;
 
     // Convert to PDF using pandoc
+    let pdf_engine_arg = format!("--pdf-engine={}", pdf_config.engine);
+    let geometry_arg = format!("geometry:margin={}", pdf_config.margin);
+    let mainfont_arg = format!("mainfont:{}", pdf_config.mainfont);
+    let monofont_arg = format!("monofont:{}", pdf_config.monofont);
+    let fontsize_arg = format!("fontsize={}", pdf_config.fontsize);
+    let papersize_arg = format!("papersize={}", pdf_config.papersize);
+    let toc_depth_arg = format!("--toc-depth={}", pdf_config.toc_depth);
+
     let output = Command::new("pandoc")
         .args([
             md_path.to_str().unwrap(),
             "-o", pdf_path.to_str().unwrap(),
-            "--pdf-engine=xelatex",
-            "-V", "geometry:margin=0.7in",
-            "-V", "mainfont:Helvetica",
-            "-V", "monofont:Menlo",
-            "-V", "fontsize=9pt",
-            "-V", "papersize=a4",
+            &pdf_engine_arg,
+            "-V", &geometry_arg,
+            "-V", &mainfont_arg,
+            "-V", &monofont_arg,
+            "-V", &fontsize_arg,
+            "-V", &papersize_arg,
             "-V", "verbatim-font-size=8pt",
             "-V", "fancyhdr=false",
             "-V", "table-use-line-widths=true",
             "-V", "tables=true",
             "-B", title_path.to_str().unwrap(),
             "--toc",
-            "--toc-depth=2",
+            &toc_depth_arg,
             "-f", "markdown+emoji",
         ])
         .output()
@@ -1127,56 +1158,65 @@ fn convert_project_to_pdf(project_dir: &Path, pdf_dir: &Path) -> Result<PathBuf,
     }
 }
 
-/// Process all projects in output directory and convert to PDFs
-fn process_projects_to_pdf(output_dir: &Path, pdf_dir: &Path) -> Vec<PathBuf> {
+/// Convert a single project directory to PDF, logging as `process_projects_to_pdf`
+/// did inline before it was parallelized.
+fn convert_and_log_project(path: &Path, pdf_dir: &Path, deepwiki_config: &config::Config) -> Option<PathBuf> {
+    let project_name = path.file_name().and_then(|f| f.to_str()).unwrap_or("unknown");
+    println!("Converting project: {}", project_name);
+
+    match convert_project_to_pdf(path, pdf_dir, deepwiki_config) {
+        Ok(pdf_path) => {
+            println!("  Created: {}", pdf_path.display());
+            Some(pdf_path)
+        }
+        Err(e) => {
+            eprintln!("  Error: {}", e);
+            None
+        }
+    }
+}
+
+fn process_projects_to_pdf(output_dir: &Path, pdf_dir: &Path, deepwiki_config: &config::Config) -> Vec<PathBuf> {
     // Create pdf_dir if it doesn't exist
     if let Err(e) = fs::create_dir_all(pdf_dir) {
         eprintln!("Error creating PDF output directory: {}", e);
         return Vec::new();
     }
 
-    let mut generated_pdfs = Vec::new();
-
     // Find all project directories (directories containing README.md or .md files)
-    for entry in fs::read_dir(output_dir).into_iter().flatten().filter_map(|e| e.ok()) {
-        let path = entry.path();
-        if !path.is_dir() {
-            continue;
-        }
-
-        // Check if it's a project directory (has markdown files)
-        let has_md_files = fs::read_dir(&path)
-            .into_iter()
-            .flatten()
-            .filter_map(|e| e.ok())
-            .any(|e| e.path().extension().map_or(false, |ext| ext == "md"));
-
-        if !has_md_files {
-            continue;
-        }
+    let project_dirs: Vec<PathBuf> = fs::read_dir(output_dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|path| {
+            path.is_dir()
+                && fs::read_dir(path)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|e| e.ok())
+                    .any(|e| is_markdown(&e.path()))
+        })
+        .collect();
 
-        let project_name = path.file_name()
-            .and_then(|f| f.to_str())
-            .unwrap_or("unknown");
+    // Each project spawns its own xelatex/pandoc run, so converting them
+    // on a parallel iterator is where most of the wall-clock time is spent.
+    project_dirs
+        .into_par_iter()
+        .filter_map(|path| convert_and_log_project(&path, pdf_dir, deepwiki_config))
+        .collect()
+}
 
-        println!("Converting project: {}", project_name);
+fn main() {
+    let args = Args::parse();
 
-        match convert_project_to_pdf(&path, pdf_dir) {
-            Ok(pdf_path) => {
-                println!("  Created: {}", pdf_path.display());
-                generated_pdfs.push(pdf_path);
-            }
-            Err(e) => {
-                eprintln!("  Error: {}", e);
-            }
+    if let Some(jobs) = args.jobs {
+        if let Err(e) = rayon::ThreadPoolBuilder::new().num_threads(jobs).build_global() {
+            eprintln!("Warning: failed to set thread pool size to {}: {}", jobs, e);
         }
     }
 
-    generated_pdfs
-}
-
-fn main() {
-    let args = Args::parse();
+    let deepwiki_config = config::load(args.config.as_deref());
 
     // Handle --pdf mode: only convert existing output to PDF
     if args.pdf {
@@ -1199,11 +1239,75 @@ fn main() {
         }
 
         println!("Converting projects to PDF...");
-        let pdfs = process_projects_to_pdf(&output_dir, &args.pdf_dir);
+        let pdfs = process_projects_to_pdf(&output_dir, &args.pdf_dir, &deepwiki_config);
         println!("\nGenerated {} PDF file(s)", pdfs.len());
         return;
     }
 
+    // Handle --html mode: only render existing output to standalone HTML
+    if args.html {
+        let output_dir = args.output_dir.clone().unwrap_or_else(|| PathBuf::from("./output"));
+
+        if !output_dir.exists() {
+            eprintln!("Error: Output directory '{}' does not exist. Run without --html first to generate markdown.", output_dir.display());
+            std::process::exit(1);
+        }
+
+        println!("Rendering output to HTML...");
+        let rendered = html_render::process_directory_to_html(&output_dir, &args.html_dir);
+        println!("\nGenerated {} HTML file(s)", rendered.len());
+        return;
+    }
+
+    // Handle --json mode: only emit the structured document model
+    if let Some(json_dir) = &args.json {
+        let output_dir = args.output_dir.clone().unwrap_or_else(|| PathBuf::from("./output"));
+
+        if !output_dir.exists() {
+            eprintln!("Error: Output directory '{}' does not exist. Run without --json first to generate markdown.", output_dir.display());
+            std::process::exit(1);
+        }
+
+        println!("Emitting JSON document model...");
+        let written = json_model::process_directory_to_json(&output_dir, json_dir);
+        println!("\nWrote {} JSON file(s)", written.len());
+        return;
+    }
+
+    // Handle --check-links mode: validate links in existing output
+    if args.check_links {
+        let output_dir = args.output_dir.clone().unwrap_or_else(|| PathBuf::from("./output"));
+
+        if !output_dir.exists() {
+            eprintln!("Error: Output directory '{}' does not exist. Run without --check-links first to generate markdown.", output_dir.display());
+            std::process::exit(1);
+        }
+
+        let broken = link_check::check_links(&output_dir);
+        link_check::report(&broken);
+        if !broken.is_empty() {
+            eprintln!("\n{} broken link(s) found", broken.len());
+            std::process::exit(1);
+        }
+        println!("No broken links found");
+        return;
+    }
+
+    // Handle --mdbook mode: only emit SUMMARY.md/book.toml for existing output
+    if args.mdbook {
+        let output_dir = args.output_dir.clone().unwrap_or_else(|| PathBuf::from("./output"));
+
+        if !output_dir.exists() {
+            eprintln!("Error: Output directory '{}' does not exist. Run without --mdbook first to generate markdown.", output_dir.display());
+            std::process::exit(1);
+        }
+
+        println!("Generating mdBook project(s)...");
+        let written = mdbook_gen::process_directory_to_mdbook(&output_dir);
+        println!("\nWrote {} file(s)", written.len());
+        return;
+    }
+
     let output_dir = if args.in_place {
         args.input_dir.clone()
     } else {
@@ -1216,7 +1320,7 @@ fn main() {
         }
     };
 
-    let changed = process_directory(&args.input_dir, &output_dir, args.dry_run);
+    let changed = process_directory(&args.input_dir, &output_dir, args.dry_run, args.eol);
 
     if args.dry_run {
         for path in changed {
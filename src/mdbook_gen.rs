@@ -0,0 +1,98 @@
+//! `--mdbook` output target: for each project directory, generate a
+//! `SUMMARY.md` and `book.toml` so `mdbook build`/`serve` can turn the
+//! cleaned wiki into a browsable HTML book, reusing the same
+//! README-driven chapter order `convert_project_to_pdf` already
+//! consolidates into a single PDF.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+
+use crate::{get_sorted_markdown_files, is_markdown};
+
+fn book_toml(project_name: &str) -> String {
+    format!(
+        "[book]\ntitle = \"{}\"\nsrc = \".\"\n\n[build]\nbuild-dir = \"book\"\n",
+        project_name.replace('"', "\\\"")
+    )
+}
+
+/// Build the `- [Title](./NN-file.md)` list mdBook's summary parser
+/// expects, in the same order `get_sorted_markdown_files` already
+/// establishes for PDF consolidation (ordinal prefixes are applied
+/// earlier, by `apply_readme_ordinal`). The list is flat: every chapter
+/// is top-level, and any nesting in the README's own list is not
+/// reproduced. Titles come from the file stem, not the document heading.
+fn summary_md(project_dir: &Path) -> String {
+    let numbered_re = Regex::new(r"^\d{2}-").unwrap();
+    let mut summary = String::from("# Summary\n\n");
+
+    if project_dir.join("README.md").exists() {
+        summary.push_str("[Introduction](./README.md)\n\n");
+    }
+
+    for file_path in get_sorted_markdown_files(project_dir) {
+        let file_name = file_path
+            .file_name()
+            .and_then(|f| f.to_str())
+            .unwrap_or("section.md");
+        let stem = file_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("Section");
+        let title = numbered_re
+            .replace(stem, "")
+            .replace(['-', '_'], " ");
+        summary.push_str(&format!("- [{}](./{})\n", title, file_name));
+    }
+
+    summary
+}
+
+/// Generate `SUMMARY.md` and `book.toml` for `project_dir`, writing them
+/// alongside its markdown files, and return the paths written.
+pub fn generate_book(project_dir: &Path) -> Vec<PathBuf> {
+    let project_name = project_dir
+        .file_name()
+        .and_then(|f| f.to_str())
+        .unwrap_or("book");
+
+    let summary_path = project_dir.join("SUMMARY.md");
+    let book_toml_path = project_dir.join("book.toml");
+
+    let mut written = Vec::new();
+    if fs::write(&summary_path, summary_md(project_dir)).is_ok() {
+        written.push(summary_path);
+    }
+    if fs::write(&book_toml_path, book_toml(project_name)).is_ok() {
+        written.push(book_toml_path);
+    }
+    written
+}
+
+/// Generate an mdBook project for every project directory (one containing
+/// markdown files) under `output_dir`.
+pub fn process_directory_to_mdbook(output_dir: &Path) -> Vec<PathBuf> {
+    let mut written = Vec::new();
+
+    for entry in fs::read_dir(output_dir).into_iter().flatten().filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+
+        let has_md_files = fs::read_dir(&path)
+            .into_iter()
+            .flatten()
+            .filter_map(|e| e.ok())
+            .any(|e| is_markdown(&e.path()));
+        if !has_md_files {
+            continue;
+        }
+
+        written.extend(generate_book(&path));
+    }
+
+    written
+}
@@ -0,0 +1,138 @@
+//! External preprocessor protocol, modeled on mdBook's own
+//! `[[preprocessor]]` mechanism: before `consolidate_project_markdown`
+//! runs, each declared command gets the project's file set piped to it as
+//! JSON over stdin and can hand back a transformed set on stdout (custom
+//! admonition expansion, glossary injection, etc.) without the crate
+//! needing a new built-in mode for every such transform.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::PreprocessorConfig;
+use crate::get_sorted_markdown_files;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct FileDocument {
+    pub path: PathBuf,
+    pub content: String,
+}
+
+/// Gather `project_dir`'s README (if any) plus its sorted markdown
+/// sections into the `{path, content}` document set the protocol passes
+/// to preprocessors, in the same order `consolidate_project_markdown`
+/// would otherwise read them in.
+pub fn collect_project_documents(project_dir: &Path) -> Vec<FileDocument> {
+    let mut documents = Vec::new();
+
+    let readme_path = project_dir.join("README.md");
+    if let Ok(content) = fs::read_to_string(&readme_path) {
+        documents.push(FileDocument { path: PathBuf::from("README.md"), content });
+    }
+
+    for file_path in get_sorted_markdown_files(project_dir) {
+        if let Ok(content) = fs::read_to_string(&file_path) {
+            let rel_path = file_path.strip_prefix(project_dir).unwrap_or(&file_path).to_path_buf();
+            documents.push(FileDocument { path: rel_path, content });
+        }
+    }
+
+    documents
+}
+
+/// Build a `Command` that runs `command_line` through the platform shell,
+/// the same way mdBook's own `[[preprocessor]]` runner does, so a
+/// configured `command = "python3 admonitions.py"` is split into
+/// executable + arguments by the shell rather than treated as one
+/// literal (non-existent) executable path.
+fn shell_command(command_line: &str) -> Command {
+    if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.args(["/C", command_line]);
+        command
+    } else {
+        let mut command = Command::new("sh");
+        command.args(["-c", command_line]);
+        command
+    }
+}
+
+/// Ask a preprocessor whether it wants to run for `renderer` via mdBook's
+/// `supports <renderer>` handshake: a success exit code means yes,
+/// anything else means skip it.
+fn supports(command: &str, renderer: &str) -> bool {
+    let command_line = format!("{} supports {}", command, renderer);
+    shell_command(&command_line)
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+/// Pipe `documents` through `command`'s stdin as a JSON array and parse
+/// the transformed array back from stdout. Stdin is fed from a separate
+/// thread while the main thread waits on the child, the way
+/// `std::process::Child`'s own docs recommend for two-way piping:
+/// writing the full payload before reading stdout would deadlock once
+/// either side fills its pipe buffer (trivial for a whole wiki's worth of
+/// markdown).
+fn run_preprocessor(command: &str, documents: &[FileDocument]) -> Result<Vec<FileDocument>, String> {
+    let input = serde_json::to_vec(documents).map_err(|e| e.to_string())?;
+
+    let mut child = shell_command(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to spawn preprocessor '{}': {}", command, e))?;
+
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| "failed to open preprocessor stdin".to_string())?;
+    let writer = thread::spawn(move || stdin.write_all(&input));
+
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+
+    // Check the child's own exit status first: a preprocessor that exits
+    // (successfully or not) without fully draining stdin makes the
+    // writer thread's `write_all` fail with a broken-pipe error, which
+    // would otherwise mask the real failure reported via stderr/exit
+    // code.
+    if !output.status.success() {
+        return Err(format!(
+            "preprocessor '{}' exited with {}: {}",
+            command,
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    writer
+        .join()
+        .map_err(|_| format!("preprocessor '{}' stdin writer thread panicked", command))?
+        .map_err(|e| e.to_string())?;
+
+    serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())
+}
+
+/// Run every configured preprocessor that supports the `"pdf"` renderer,
+/// in declaration order, over `documents`. A preprocessor that opts out
+/// via the `supports` handshake, or that fails, is skipped and the prior
+/// document set is passed on unchanged.
+pub fn run_all(preprocessors: &[PreprocessorConfig], mut documents: Vec<FileDocument>) -> Vec<FileDocument> {
+    for preprocessor in preprocessors {
+        if !supports(&preprocessor.command, "pdf") {
+            continue;
+        }
+        match run_preprocessor(&preprocessor.command, &documents) {
+            Ok(transformed) => documents = transformed,
+            Err(e) => eprintln!("Warning: preprocessor '{}' failed: {}", preprocessor.command, e),
+        }
+    }
+
+    documents
+}
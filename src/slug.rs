@@ -0,0 +1,32 @@
+//! GitHub-style heading-anchor slugification, shared by everything that
+//! needs to turn rendered heading text into a stable identifier: the
+//! `--json` outline, title-aware file renaming, and `--check-links`
+//! anchor validation.
+
+/// Lowercase ASCII letters, drop anything that isn't alphanumeric, `_`,
+/// `-` or whitespace, and turn each run of whitespace and dashes into a
+/// single `-`, with no leading or trailing `-`. Underscores are kept
+/// as-is. Unlike GitHub's own anchors, `A - B` becomes `a-b`, not `a---b`.
+pub(crate) fn slugify(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    let mut last_was_dash = false;
+
+    for ch in text.trim().chars() {
+        if ch.is_alphanumeric() || ch == '_' {
+            slug.push(ch.to_ascii_lowercase());
+            last_was_dash = false;
+        } else if ch == '-' || ch.is_whitespace() {
+            if !last_was_dash && !slug.is_empty() {
+                slug.push('-');
+                last_was_dash = true;
+            }
+        }
+        // everything else (punctuation) is simply dropped
+    }
+
+    while slug.ends_with('-') {
+        slug.pop();
+    }
+
+    slug
+}